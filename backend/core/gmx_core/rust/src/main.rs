@@ -1,13 +1,28 @@
-use std::{fs, path::PathBuf, str::FromStr, time::SystemTime};
+use std::{
+    fs,
+    io::{self, Read},
+    path::PathBuf,
+    str::FromStr,
+    time::SystemTime,
+};
 
 use anyhow::{anyhow, Result};
+use argon2::Argon2;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use bip39::{Language, Mnemonic, Seed};
 use bs58;
+use chacha20poly1305::{
+    aead::{Aead, KeyInit},
+    XChaCha20Poly1305, XNonce,
+};
 use clap::{Parser, Subcommand};
 use ed25519_dalek::{Keypair, PublicKey, SecretKey};
-use serde::Serialize;
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use slip10::{BIP32Path, Curve};
+use solana_sdk::pubkey::Pubkey;
+use zeroize::Zeroizing;
 
 mod ops;
 
@@ -26,9 +41,22 @@ struct Cli {
     #[arg(long)]
     signer: Option<PathBuf>,
 
-    /// Print only JSON to stdout
+    /// BIP32 derivation path for mnemonic signers (overrides --account-index)
     #[arg(long)]
-    json: bool,
+    derivation_path: Option<String>,
+
+    /// BIP44 account index, used to build m/44'/501'/<index>'/0' when --derivation-path is absent
+    #[arg(long, default_value_t = 0)]
+    account_index: u32,
+
+    /// BIP39 passphrase (the "25th word") for plaintext mnemonic signers; for an
+    /// encrypted signer file and for lock/unlock it is the file's encryption passphrase
+    #[arg(long)]
+    passphrase: Option<String>,
+
+    /// Read the passphrase from stdin instead of --passphrase
+    #[arg(long)]
+    passphrase_stdin: bool,
 }
 
 #[derive(Subcommand, Debug)]
@@ -39,19 +67,66 @@ enum Commands {
     Markets,
     /// List your perp positions (stub until SDK wired)
     Positions,
-    /// Deposit USDC (stub placeholder)
-    Deposit { amount: f64 },
-    /// Open position (stub placeholder)
+    /// Show native SOL and USDC balances for the wallet
+    Balance,
+    /// Request a devnet SOL airdrop for the wallet
+    Airdrop { sol: f64 },
+    /// Deposit USDC into a market's GM pool
+    Deposit {
+        market: String,
+        amount: f64,
+    },
+    /// Open (or add to) a position
     Open {
         market: String,
         side: String,
         size_usd: f64,
         #[arg(long)] price: Option<f64>,
     },
-    /// Close position (stub placeholder)
-    Close { market: String },
-    /// Cancel order by id (stub placeholder)
+    /// Close (or reduce) a position
+    Close {
+        market: String,
+        side: String,
+        /// Collateral token mint for the position (positions are keyed by
+        /// owner + market + collateral + side)
+        collateral: String,
+    },
+    /// Cancel order by id
     Cancel { order_id: String },
+    /// Generate a new mnemonic and derive its wallet
+    Keygen {
+        /// Word count for the generated mnemonic (12 or 24)
+        #[arg(long, default_value_t = 12)]
+        words: u8,
+        /// Write the mnemonic to this file (0600) instead of printing it
+        #[arg(long)]
+        outfile: Option<PathBuf>,
+        /// Overwrite `outfile` if it already exists
+        #[arg(long)]
+        force: bool,
+    },
+    /// Encrypt a plaintext mnemonic file with a passphrase
+    Lock {
+        /// Plaintext mnemonic file to encrypt (defaults to the resolved signer)
+        #[arg(long)]
+        infile: Option<PathBuf>,
+        /// Where to write the encrypted container
+        outfile: PathBuf,
+        /// Overwrite `outfile` if it already exists
+        #[arg(long)]
+        force: bool,
+    },
+    /// Decrypt an encrypted signer file back to a plaintext mnemonic
+    Unlock {
+        /// Encrypted signer file to decrypt (defaults to the resolved signer)
+        #[arg(long)]
+        infile: Option<PathBuf>,
+        /// Where to write the decrypted mnemonic
+        outfile: PathBuf,
+        /// Overwrite `outfile` if it already exists
+        #[arg(long)]
+        force: bool,
+    },
 }
 
 #[derive(Debug)]
@@ -64,8 +139,8 @@ struct Wallet {
 enum WalletErr {
     #[error("signer not found")]
     NotFound,
-    #[error("unsupported signer file format")]
-    Unsupported,
+    #[error("unsupported signer file format: {0}")]
+    Unsupported(String),
     #[error("invalid mnemonic: {0}")]
     BadMnemonic(String),
     #[error("derivation failed: {0}")]
@@ -77,20 +152,37 @@ fn read_text(path: &PathBuf) -> Result<String> {
 }
 
 fn try_find_signer_txt() -> Option<PathBuf> {
-    // Walk up a few parents to find repo-root/signerr.txt
+    // Walk up a few parents to find a repo-root signer.txt or a named Solana
+    // CLI / gmx_runner keypair file. We only match these exact names — not
+    // "any *.json" — so an unrelated JSON file in the cwd or an ancestor
+    // (package.json, tsconfig.json, ...) is never silently picked as a signer.
     let mut cur = std::env::current_dir().ok()?;
     for _ in 0..6 {
-        let candidate = cur.join("signer.txt");
-        if candidate.exists() {
-            return Some(candidate);
+        for name in ["signer.txt", "id.json", "signer.json"] {
+            let candidate = cur.join(name);
+            if candidate.exists() {
+                return Some(candidate);
+            }
         }
         cur = cur.parent()?.to_path_buf();
     }
     None
 }
 
-fn wallet_from_mnemonic_words(words: &str) -> std::result::Result<Wallet, WalletErr> {
-    let clean = words.trim().replace('\n', " ");
+fn derivation_path_for(explicit: &Option<String>, account_index: u32) -> std::result::Result<BIP32Path, WalletErr> {
+    let raw = explicit
+        .clone()
+        .unwrap_or_else(|| format!("m/44'/501'/{account_index}'/0'"));
+    BIP32Path::from_str(&raw).map_err(|e| WalletErr::Derive(format!("bad derivation path {raw}: {e}")))
+}
+
+fn wallet_from_mnemonic_words(
+    words: &str,
+    derivation_path: &Option<String>,
+    account_index: u32,
+    passphrase: &str,
+) -> std::result::Result<Wallet, WalletErr> {
+    let clean = Zeroizing::new(words.trim().replace('\n', " "));
     let wc = clean.split_whitespace().count();
     if wc != 12 && wc != 24 {
         return Err(WalletErr::BadMnemonic(format!(
@@ -98,12 +190,10 @@ fn wallet_from_mnemonic_words(words: &str) -> std::result::Result<Wallet, Wallet
             wc
         )));
     }
-    let mnemonic = Mnemonic::parse_in(Language::English, clean)
+    let mnemonic = Mnemonic::parse_in(Language::English, clean.as_str())
         .map_err(|e| WalletErr::BadMnemonic(e.to_string()))?;
-    let seed = Seed::new(&mnemonic, ""); // no passphrase
-    // Standard Solana path: m/44'/501'/0'/0'
-    let path = BIP32Path::from_str("m/44'/501'/0'/0'")
-        .map_err(|e| WalletErr::Derive(e.to_string()))?;
+    let seed = Seed::new(&mnemonic, passphrase);
+    let path = derivation_path_for(derivation_path, account_index)?;
     let derived =
         slip10::derive_key_from_path(seed.as_bytes(), Curve::Ed25519, &path)
             .map_err(|e| WalletErr::Derive(e.to_string()))?;
@@ -120,47 +210,350 @@ fn wallet_from_mnemonic_words(words: &str) -> std::result::Result<Wallet, Wallet
     Ok(Wallet { keypair, pubkey_bs58 })
 }
 
-fn wallet_from_signer_path(path: &PathBuf) -> std::result::Result<Wallet, WalletErr> {
-    // If file looks like JSON keypair array, we could support it later.
-    // For now we expect 12/24-word mnemonic in signer.txt.
+/// At-rest container for an encrypted mnemonic: Argon2id-derived key, XChaCha20-Poly1305 AEAD.
+#[derive(Serialize, Deserialize)]
+struct EncryptedSigner {
+    magic: String,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+const ENC_MAGIC: &str = "gmx-runner-signer-v1";
+const ENC_SALT_LEN: usize = 16;
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> std::result::Result<Zeroizing<[u8; 32]>, WalletErr> {
+    let mut key = Zeroizing::new([0u8; 32]);
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, key.as_mut())
+        .map_err(|e| WalletErr::Derive(format!("argon2 key derivation failed: {e}")))?;
+    Ok(key)
+}
+
+fn encrypt_mnemonic(phrase: &str, passphrase: &str) -> Result<EncryptedSigner> {
+    let mut salt = [0u8; ENC_SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let key = derive_key(passphrase, &salt)?;
+
+    let mut nonce_bytes = [0u8; 24];
+    OsRng.fill_bytes(&mut nonce_bytes);
+    let cipher = XChaCha20Poly1305::new(key.as_slice().into());
+    let ciphertext = cipher
+        .encrypt(XNonce::from_slice(&nonce_bytes), phrase.as_bytes())
+        .map_err(|e| anyhow!("encryption failed: {e}"))?;
+
+    Ok(EncryptedSigner {
+        magic: ENC_MAGIC.to_string(),
+        salt: BASE64.encode(salt),
+        nonce: BASE64.encode(nonce_bytes),
+        ciphertext: BASE64.encode(ciphertext),
+    })
+}
+
+/// Decrypt a container back into its mnemonic phrase. The returned buffer
+/// zeroizes itself once the caller is done deriving a `Keypair` from it.
+fn decrypt_mnemonic(
+    container: &EncryptedSigner,
+    passphrase: &str,
+) -> std::result::Result<Zeroizing<String>, WalletErr> {
+    if container.magic != ENC_MAGIC {
+        return Err(WalletErr::Unsupported(format!(
+            "unrecognized signer container magic {:?}",
+            container.magic
+        )));
+    }
+    let salt = BASE64
+        .decode(&container.salt)
+        .map_err(|e| WalletErr::Unsupported(e.to_string()))?;
+    let nonce_bytes = BASE64
+        .decode(&container.nonce)
+        .map_err(|e| WalletErr::Unsupported(e.to_string()))?;
+    let ciphertext = BASE64
+        .decode(&container.ciphertext)
+        .map_err(|e| WalletErr::Unsupported(e.to_string()))?;
+
+    let key = derive_key(passphrase, &salt)?;
+    let cipher = XChaCha20Poly1305::new(key.as_slice().into());
+    let plaintext = cipher
+        .decrypt(XNonce::from_slice(&nonce_bytes), ciphertext.as_ref())
+        .map_err(|_| WalletErr::Derive("decryption failed: wrong passphrase or corrupt file".into()))?;
+    let phrase = String::from_utf8(plaintext).map_err(|e| WalletErr::BadMnemonic(e.to_string()))?;
+    Ok(Zeroizing::new(phrase))
+}
+
+fn wallet_from_keypair_json(content: &str) -> std::result::Result<Wallet, WalletErr> {
+    let raw: Vec<u8> =
+        serde_json::from_str(content).map_err(|e| WalletErr::Unsupported(e.to_string()))?;
+    if raw.len() != 64 {
+        return Err(WalletErr::Unsupported(format!(
+            "expected 64 bytes in keypair array, got {}",
+            raw.len()
+        )));
+    }
+    let keypair = Keypair::from_bytes(&raw).map_err(|e| WalletErr::Derive(e.to_string()))?;
+    let pubkey_bs58 = bs58::encode(keypair.public.to_bytes()).into_string();
+    Ok(Wallet { keypair, pubkey_bs58 })
+}
+
+fn wallet_from_signer_path(
+    path: &PathBuf,
+    derivation_path: &Option<String>,
+    account_index: u32,
+    passphrase: &str,
+) -> std::result::Result<Wallet, WalletErr> {
     let content = read_text(path).map_err(|_| WalletErr::NotFound)?;
-    wallet_from_mnemonic_words(&content)
+    let trimmed = content.trim();
+    if trimmed.starts_with('[') {
+        wallet_from_keypair_json(trimmed)
+    } else if let Ok(container) = serde_json::from_str::<EncryptedSigner>(trimmed) {
+        // The container's passphrase unlocks the mnemonic itself, so no
+        // separate BIP39 ("25th word") passphrase applies on top of it.
+        let phrase = decrypt_mnemonic(&container, passphrase)?;
+        wallet_from_mnemonic_words(&phrase, derivation_path, account_index, "")
+    } else {
+        wallet_from_mnemonic_words(&content, derivation_path, account_index, passphrase)
+    }
+}
+
+fn generate_mnemonic(words: u8) -> Result<Mnemonic> {
+    let entropy_len = match words {
+        12 => 16,
+        24 => 32,
+        other => return Err(anyhow!("--words must be 12 or 24, got {other}")),
+    };
+    let mut entropy = vec![0u8; entropy_len];
+    OsRng.fill_bytes(&mut entropy);
+    Mnemonic::from_entropy_in(Language::English, &entropy)
+        .map_err(|e| anyhow!(WalletErr::Derive(e.to_string())))
+}
+
+fn run_keygen(cli: &Cli, words: u8, outfile: &Option<PathBuf>, force: bool) -> Result<()> {
+    let mnemonic = generate_mnemonic(words)?;
+    let phrase = mnemonic.to_string();
+    let passphrase = read_passphrase(cli)?;
+    let wallet = wallet_from_mnemonic_words(&phrase, &cli.derivation_path, cli.account_index, &passphrase)?;
+
+    match outfile {
+        Some(path) => {
+            if path.exists() && !force {
+                return Err(anyhow!(
+                    "refusing to overwrite existing file {}: pass --force",
+                    path.display()
+                ));
+            }
+            write_private_file(path, &phrase)?;
+            print_json(&json!({
+                "ok": true,
+                "pubkey": wallet.pubkey_bs58,
+                "path": path,
+            }));
+        }
+        None => {
+            print_json(&json!({
+                "ok": true,
+                "pubkey": wallet.pubkey_bs58,
+                "mnemonic": phrase,
+            }));
+        }
+    }
+    Ok(())
 }
 
-fn resolve_wallet(signer_flag: &Option<PathBuf>) -> Result<Wallet> {
-    let path = if let Some(p) = signer_flag {
+fn write_private_file(path: &PathBuf, contents: &str) -> Result<()> {
+    fs::write(path, contents)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+    }
+    Ok(())
+}
+
+fn signer_path_or_default(infile: &Option<PathBuf>, cli: &Cli) -> Result<PathBuf> {
+    infile
+        .clone()
+        .or_else(|| cli.signer.clone())
+        .or_else(try_find_signer_txt)
+        .ok_or_else(|| anyhow!(WalletErr::NotFound))
+}
+
+fn run_lock(cli: &Cli, infile: &Option<PathBuf>, outfile: &PathBuf, force: bool) -> Result<()> {
+    let path = signer_path_or_default(infile, cli)?;
+    let content = read_text(&path)?;
+    let trimmed = content.trim();
+    if trimmed.starts_with('[') {
+        return Err(anyhow!("cannot lock a JSON keypair file; only mnemonics are supported"));
+    }
+    let passphrase = read_passphrase(cli)?;
+    if passphrase.is_empty() {
+        return Err(anyhow!("--passphrase or --passphrase-stdin is required to lock a signer file"));
+    }
+    if outfile.exists() && !force {
+        return Err(anyhow!(
+            "refusing to overwrite existing file {}: pass --force",
+            outfile.display()
+        ));
+    }
+    let container = encrypt_mnemonic(trimmed, &passphrase)?;
+    write_private_file(outfile, &serde_json::to_string_pretty(&container)?)?;
+    print_json(&json!({ "ok": true, "path": outfile }));
+    Ok(())
+}
+
+fn run_unlock(cli: &Cli, infile: &Option<PathBuf>, outfile: &PathBuf, force: bool) -> Result<()> {
+    let path = signer_path_or_default(infile, cli)?;
+    let content = read_text(&path)?;
+    let container: EncryptedSigner = serde_json::from_str(content.trim())
+        .map_err(|e| anyhow!(WalletErr::Unsupported(e.to_string())))?;
+    let passphrase = read_passphrase(cli)?;
+    let phrase = decrypt_mnemonic(&container, &passphrase)?;
+    if outfile.exists() && !force {
+        return Err(anyhow!(
+            "refusing to overwrite existing file {}: pass --force",
+            outfile.display()
+        ));
+    }
+    write_private_file(outfile, &phrase)?;
+    print_json(&json!({ "ok": true, "path": outfile }));
+    Ok(())
+}
+
+fn read_passphrase(cli: &Cli) -> Result<String> {
+    if cli.passphrase_stdin {
+        let mut buf = String::new();
+        io::stdin().read_to_string(&mut buf)?;
+        Ok(buf.trim_end_matches(['\n', '\r']).to_string())
+    } else {
+        Ok(cli.passphrase.clone().unwrap_or_default())
+    }
+}
+
+fn resolve_wallet(cli: &Cli) -> Result<Wallet> {
+    let path = if let Some(p) = &cli.signer {
         p.to_path_buf()
     } else if let Some(p) = try_find_signer_txt() {
         p
     } else {
         return Err(anyhow!(WalletErr::NotFound));
     };
-    Ok(wallet_from_signer_path(&path)?)
+    let passphrase = read_passphrase(cli)?;
+    Ok(wallet_from_signer_path(
+        &path,
+        &cli.derivation_path,
+        cli.account_index,
+        &passphrase,
+    )?)
 }
 
+/// Stable JSON envelope for any top-level failure, so callers can branch on
+/// `code` and `command` instead of scraping free-text error messages.
 #[derive(Serialize)]
-struct ErrorBody<'a> {
+struct ErrorEnvelope {
     ok: bool,
     code: u16,
-    err: &'a str,
+    err: String,
+    command: String,
+    ts: u64,
 }
 
 fn print_json<T: Serialize>(val: &T) {
     println!("{}", serde_json::to_string_pretty(val).unwrap());
 }
 
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+fn command_name(command: &Commands) -> &'static str {
+    match command {
+        Commands::Health => "health",
+        Commands::Markets => "markets",
+        Commands::Positions => "positions",
+        Commands::Balance => "balance",
+        Commands::Airdrop { .. } => "airdrop",
+        Commands::Deposit { .. } => "deposit",
+        Commands::Open { .. } => "open",
+        Commands::Close { .. } => "close",
+        Commands::Cancel { .. } => "cancel",
+        Commands::Keygen { .. } => "keygen",
+        Commands::Lock { .. } => "lock",
+        Commands::Unlock { .. } => "unlock",
+    }
+}
+
+/// Map any error surfaced by the CLI to a stable numeric code: wallet-not-found
+/// -> 404, unsupported signer format -> 415, bad mnemonic -> 422, derivation
+/// failure -> 500, bad cluster -> 400, not-yet-wired op -> 501, everything else
+/// (RPC/simulation) -> 502.
+fn error_code(err: &anyhow::Error) -> u16 {
+    if let Some(e) = err.downcast_ref::<WalletErr>() {
+        return match e {
+            WalletErr::NotFound => 404,
+            WalletErr::Unsupported(_) => 415,
+            WalletErr::BadMnemonic(_) => 422,
+            WalletErr::Derive(_) => 500,
+        };
+    }
+    if let Some(e) = err.downcast_ref::<ops::OpsErr>() {
+        return match e {
+            ops::OpsErr::BadCluster(_) => 400,
+            ops::OpsErr::NotImplemented(_) => 501,
+        };
+    }
+    502
+}
+
+/// Client errors (4xx) exit 1; server/RPC errors (5xx) exit 2.
+fn exit_status_for(code: u16) -> i32 {
+    if (400..500).contains(&code) {
+        1
+    } else {
+        2
+    }
+}
+
+fn parse_side(side: &str) -> Result<bool> {
+    match side.to_ascii_lowercase().as_str() {
+        "long" => Ok(true),
+        "short" => Ok(false),
+        other => Err(anyhow!("side must be \"long\" or \"short\", got {other:?}")),
+    }
+}
+
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() {
     let cli = Cli::parse();
-    let wallet = resolve_wallet(&cli.signer)?;
+    let command = command_name(&cli.command).to_string();
+    if let Err(err) = run(cli).await {
+        let code = error_code(&err);
+        print_json(&ErrorEnvelope {
+            ok: false,
+            code,
+            err: err.to_string(),
+            command,
+            ts: unix_now(),
+        });
+        std::process::exit(exit_status_for(code));
+    }
+}
+
+async fn run(cli: Cli) -> Result<()> {
+    match &cli.command {
+        Commands::Keygen { words, outfile, force } => return run_keygen(&cli, *words, outfile, *force),
+        Commands::Lock { infile, outfile, force } => return run_lock(&cli, infile, outfile, *force),
+        Commands::Unlock { infile, outfile, force } => return run_unlock(&cli, infile, outfile, *force),
+        _ => {}
+    }
+    let wallet = resolve_wallet(&cli)?;
     let now = SystemTime::now();
+    let sol_keypair = ops::to_solana_keypair(&wallet.keypair.to_bytes())?;
     match cli.command {
         Commands::Health => {
-            let body = ops::Health {
-                ok: true,
-                cluster: cli.cluster.clone(),
-                wallet_pubkey: wallet.pubkey_bs58.clone(),
-            };
+            let client = ops::build_client(&cli.cluster, &sol_keypair)?;
+            let body = ops::health(&client, &sol_keypair, &cli.cluster).await?;
             print_json(&body);
         }
         Commands::Markets => {
@@ -170,7 +563,7 @@ async fn main() -> Result<()> {
                 "cluster": cli.cluster,
                 "wallet_pubkey": wallet.pubkey_bs58,
                 "markets": markets,
-                "ts": now.duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs()
+                "ts": unix_now()
             }));
         }
         Commands::Positions => {
@@ -180,21 +573,61 @@ async fn main() -> Result<()> {
                 "cluster": cli.cluster,
                 "wallet_pubkey": wallet.pubkey_bs58,
                 "positions": positions,
-                "ts": now.duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs()
+                "ts": unix_now()
             }));
         }
-        Commands::Deposit { .. } => {
-            print_json(&ErrorBody { ok: false, code: 501, err: "deposit not yet implemented" });
+        Commands::Balance => {
+            let pubkey = Pubkey::from_str(&wallet.pubkey_bs58)?;
+            let info = ops::balance(&cli.cluster, &pubkey).await?;
+            print_json(&info);
+        }
+        Commands::Airdrop { sol } => {
+            let pubkey = Pubkey::from_str(&wallet.pubkey_bs58)?;
+            let signature = ops::airdrop(&cli.cluster, &pubkey, sol).await?;
+            emit_tx_result(signature, &cli.cluster, &wallet.pubkey_bs58, now);
+        }
+        Commands::Deposit { market, amount } => {
+            let client = ops::build_client(&cli.cluster, &sol_keypair)?;
+            let market_token = Pubkey::from_str(&market)?;
+            let signature = ops::deposit(&client, &market_token, amount).await?;
+            emit_tx_result(signature, &cli.cluster, &wallet.pubkey_bs58, now);
         }
-        Commands::Open { .. } => {
-            print_json(&ErrorBody { ok: false, code: 501, err: "open not yet implemented" });
+        Commands::Open { market, side, size_usd, price } => {
+            let client = ops::build_client(&cli.cluster, &sol_keypair)?;
+            let market_token = Pubkey::from_str(&market)?;
+            let is_long = parse_side(&side)?;
+            let signature = ops::open_position(&client, &market_token, is_long, size_usd, price).await?;
+            emit_tx_result(signature, &cli.cluster, &wallet.pubkey_bs58, now);
         }
-        Commands::Close { .. } => {
-            print_json(&ErrorBody { ok: false, code: 501, err: "close not yet implemented" });
+        Commands::Close { market, side, collateral } => {
+            let client = ops::build_client(&cli.cluster, &sol_keypair)?;
+            let owner = Pubkey::from_str(&wallet.pubkey_bs58)?;
+            let market_token = Pubkey::from_str(&market)?;
+            let collateral_token = Pubkey::from_str(&collateral)?;
+            let is_long = parse_side(&side)?;
+            let signature = ops::close_position(&client, &owner, &market_token, &collateral_token, is_long).await?;
+            emit_tx_result(signature, &cli.cluster, &wallet.pubkey_bs58, now);
         }
-        Commands::Cancel { .. } => {
-            print_json(&ErrorBody { ok: false, code: 501, err: "cancel not yet implemented" });
+        Commands::Cancel { order_id } => {
+            let client = ops::build_client(&cli.cluster, &sol_keypair)?;
+            let order = Pubkey::from_str(&order_id)?;
+            let signature = ops::cancel_order(&client, &order).await?;
+            emit_tx_result(signature, &cli.cluster, &wallet.pubkey_bs58, now);
+        }
+        Commands::Keygen { .. } | Commands::Lock { .. } | Commands::Unlock { .. } => {
+            unreachable!("handled above")
         }
     }
     Ok(())
 }
+
+/// Print a successful transaction envelope.
+fn emit_tx_result(signature: String, cluster: &str, wallet_pubkey: &str, now: SystemTime) {
+    print_json(&json!({
+        "ok": true,
+        "cluster": cluster,
+        "wallet_pubkey": wallet_pubkey,
+        "signature": signature,
+        "ts": now.duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs()
+    }));
+}
@@ -1,8 +1,12 @@
+use std::str::FromStr;
+
 use anyhow::Result;
 use serde::Serialize;
 
+use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_sdk::signature::{Keypair as SolKeypair, Signer};
 use solana_sdk::pubkey::Pubkey;
+use spl_associated_token_account::get_associated_token_address;
 
 use gmsol_sdk::{
     Client,
@@ -11,6 +15,16 @@ use gmsol_sdk::{
     pda,
 };
 
+/// Failure modes specific to this module, classified so `main` can map them
+/// to a stable error code (see `error_code` in `main.rs`).
+#[derive(thiserror::Error, Debug)]
+pub enum OpsErr {
+    #[error("unsupported cluster: {0}")]
+    BadCluster(String),
+    #[error("{0} is not yet implemented")]
+    NotImplemented(&'static str),
+}
+
 #[derive(Debug, Serialize)]
 pub struct Health {
     pub ok: bool,
@@ -19,16 +33,48 @@ pub struct Health {
     pub store_address: String,
 }
 
+/// GMX/gmsol USD values (order/position size, price) are fixed-point with 30
+/// decimals (`Precision::FLOAT_PRECISION`) and do not fit in a `u64`.
+const USD_DECIMALS: i32 = 30;
+/// USDC, the deposit collateral token, uses its SPL mint's 6 decimals.
+const USDC_DECIMALS: i32 = 6;
+
+/// Convert a human USD amount (e.g. `125.50`) into the SDK's 30-decimal
+/// fixed-point `u128` used for order/position sizes and prices.
+fn usd_to_fixed(usd: f64) -> Result<u128> {
+    let scaled = usd * 10f64.powi(USD_DECIMALS);
+    if !scaled.is_finite() || scaled < 0.0 || scaled > u128::MAX as f64 {
+        anyhow::bail!("usd amount out of range: {usd}");
+    }
+    Ok(scaled.round() as u128)
+}
+
+/// Convert a human USDC amount into the token's native 6-decimal `u64` amount.
+fn usdc_to_amount(usdc: f64) -> Result<u64> {
+    let scaled = usdc * 10f64.powi(USDC_DECIMALS);
+    if !scaled.is_finite() || scaled < 0.0 || scaled > u64::MAX as f64 {
+        anyhow::bail!("usdc amount out of range: {usdc}");
+    }
+    Ok(scaled.round() as u64)
+}
+
 /// Build a GMSOL client from a solana Keypair
 pub fn build_client(cluster: &str, wallet: &SolKeypair) -> Result<Client<'_>> {
     let cl = match cluster {
         "mainnet" => Cluster::Mainnet,
         "devnet" => Cluster::Devnet,
-        other => anyhow::bail!("unsupported cluster: {other}"),
+        other => return Err(OpsErr::BadCluster(other.to_string()).into()),
     };
     Ok(Client::new(cl, wallet)?)
 }
 
+/// Convert the ed25519-dalek keypair produced by our signer loader into the
+/// `solana_sdk` keypair the SDK client expects. Both crates lay out a keypair
+/// as `secret(32) || public(32)`, so this is a plain reinterpretation.
+pub fn to_solana_keypair(bytes: &[u8; 64]) -> Result<SolKeypair> {
+    SolKeypair::from_bytes(bytes).map_err(|e| anyhow::anyhow!("invalid keypair bytes: {e}"))
+}
+
 pub async fn health(client: &Client<'_>, wallet: &SolKeypair, cluster: &str) -> Result<Health> {
     let store = client.find_store_address("");
     Ok(Health {
@@ -39,30 +85,155 @@ pub async fn health(client: &Client<'_>, wallet: &SolKeypair, cluster: &str) ->
     })
 }
 
-/* ---- Upcoming wiring (next pass) ----
+const USDC_MINT_MAINNET: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+const USDC_MINT_DEVNET: &str = "4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU";
+const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+
+fn rpc_url(cluster: &str) -> Result<&'static str> {
+    match cluster {
+        "mainnet" => Ok("https://api.mainnet-beta.solana.com"),
+        "devnet" => Ok("https://api.devnet.solana.com"),
+        other => Err(OpsErr::BadCluster(other.to_string()).into()),
+    }
+}
+
+fn usdc_mint(cluster: &str) -> &'static str {
+    if cluster == "devnet" {
+        USDC_MINT_DEVNET
+    } else {
+        USDC_MINT_MAINNET
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Balance {
+    pub ok: bool,
+    pub cluster: String,
+    pub wallet_pubkey: String,
+    pub sol_lamports: u64,
+    pub sol: f64,
+    pub usdc_amount: Option<u64>,
+    pub usdc: Option<f64>,
+}
+
+/// Read the wallet's native SOL balance and, if the USDC ATA exists, its USDC balance.
+pub async fn balance(cluster: &str, wallet: &Pubkey) -> Result<Balance> {
+    let rpc = RpcClient::new(rpc_url(cluster)?.to_string());
+    let lamports = rpc.get_balance(wallet).await?;
+
+    let mint = Pubkey::from_str(usdc_mint(cluster))?;
+    let usdc_ata = get_associated_token_address(wallet, &mint);
+    let (usdc_amount, usdc) = match rpc.get_token_account_balance(&usdc_ata).await {
+        Ok(bal) => (bal.amount.parse::<u64>().ok(), bal.ui_amount),
+        Err(_) => (None, None),
+    };
+
+    Ok(Balance {
+        ok: true,
+        cluster: cluster.to_string(),
+        wallet_pubkey: wallet.to_string(),
+        sol_lamports: lamports,
+        sol: lamports as f64 / LAMPORTS_PER_SOL,
+        usdc_amount,
+        usdc,
+    })
+}
 
-pub async fn list_markets(client: &Client<'_>) -> Result<Vec<Market>> {
-    // Option A: discovery helpers (SDK): gmsol_sdk::discover::market::MarketDiscovery
-    // Option B: read TokenMap/Market accounts via SDK RPC helpers.
-    todo!()
+/// Request and confirm a devnet SOL airdrop. Refuses any other cluster.
+pub async fn airdrop(cluster: &str, wallet: &Pubkey, sol: f64) -> Result<String> {
+    if cluster != "devnet" {
+        return Err(OpsErr::BadCluster(format!("{cluster} (airdrop is only available on devnet)")).into());
+    }
+    let rpc = RpcClient::new(rpc_url(cluster)?.to_string());
+    let lamports = (sol * LAMPORTS_PER_SOL).round() as u64;
+    let sig = rpc.request_airdrop(wallet, lamports).await?;
+    rpc.poll_for_signature_with_commitment(
+        &sig,
+        solana_sdk::commitment_config::CommitmentConfig::confirmed(),
+    )
+    .await?;
+    Ok(sig.to_string())
 }
 
-pub async fn list_positions(client: &Client<'_>, owner: &Pubkey) -> Result<Vec<Position>> {
-    // Use PDAs + decode with SDK types:
-    //  - pda::find_position_address(...)
-    //  - fetch & serde decode to gmsol_store::states::Position
-    todo!()
+/// Open (or add to) a position via a market-increase order.
+///
+/// `acceptable_price` is `None` for a market order (best available price) or
+/// `Some(price)` when the caller supplied `--price`.
+pub async fn open_position(
+    client: &Client<'_>,
+    market_token: &Pubkey,
+    is_long: bool,
+    size_usd: f64,
+    acceptable_price: Option<f64>,
+) -> Result<String> {
+    let store = client.find_store_address("");
+    let is_market = acceptable_price.is_none();
+    let size = usd_to_fixed(size_usd)?;
+    let price = acceptable_price.map(usd_to_fixed).transpose()?.unwrap_or(0);
+    let (txn, _order) = client
+        .market_increase(&store, market_token, is_long, size, is_market, price)
+        .build_with_address()
+        .await?;
+    let sig = txn.send().await?;
+    Ok(sig.to_string())
 }
 
-pub async fn open_position(client: &Client<'_>, market_token: &Pubkey, is_long: bool,
-                           size_usd: u64, is_market: bool, acceptable_price: u128) -> Result<String> {
+/// Close (or reduce) a position via a market-decrease order.
+///
+/// GMX positions are keyed by `(owner, market, collateral_token, is_long)`, so
+/// all four must go into the PDA derivation or this would collide with other
+/// wallets' positions on the same market/side.
+pub async fn close_position(
+    client: &Client<'_>,
+    owner: &Pubkey,
+    market_token: &Pubkey,
+    collateral_token: &Pubkey,
+    is_long: bool,
+) -> Result<String> {
     let store = client.find_store_address("");
+    let position = pda::find_position_address(&store, owner, market_token, collateral_token, is_long);
     let (txn, _order) = client
-        .market_increase(&store, market_token, is_long, size_usd, is_market, acceptable_price)
+        .market_decrease(&store, &position, market_token, is_long)
         .build_with_address()
         .await?;
     let sig = txn.send().await?;
     Ok(sig.to_string())
 }
 
-*/
+/// Deposit USDC into the default store's GM pool.
+pub async fn deposit(client: &Client<'_>, market_token: &Pubkey, amount_usd: f64) -> Result<String> {
+    let store = client.find_store_address("");
+    let amount = usdc_to_amount(amount_usd)?;
+    let (txn, _deposit) = client
+        .create_deposit(&store, market_token, amount)
+        .build_with_address()
+        .await?;
+    let sig = txn.send().await?;
+    Ok(sig.to_string())
+}
+
+/// Cancel a previously submitted order by its address.
+pub async fn cancel_order(client: &Client<'_>, order: &Pubkey) -> Result<String> {
+    let store = client.find_store_address("");
+    let txn = client.close_order(&store, order)?.build().await?;
+    let sig = txn.send().await?;
+    Ok(sig.to_string())
+}
+
+/// List markets known to the default store.
+///
+/// Not wired yet: real discovery needs either
+/// `gmsol_sdk::discover::market::MarketDiscovery` or decoding `Market`
+/// accounts via the SDK's RPC helpers. Returns a 501 until that lands.
+pub async fn list_markets() -> Result<Vec<serde_json::Value>> {
+    Err(OpsErr::NotImplemented("markets").into())
+}
+
+/// List the given owner's open positions.
+///
+/// Not wired yet: real discovery needs `pda::find_position_address` per
+/// known market/collateral pair plus decoding `gmsol_store::states::Position`
+/// accounts. Returns a 501 until that lands.
+pub async fn list_positions(_owner: &str) -> Result<Vec<serde_json::Value>> {
+    Err(OpsErr::NotImplemented("positions").into())
+}